@@ -1,24 +1,39 @@
 #![feature(test)]
+// `anellus_tsan` is a deliberate custom cfg enabled via RUSTFLAGS for the
+// ThreadSanitizer stress module at the bottom of this file.
+#![allow(unexpected_cfgs)]
 extern crate test;
 
-use core::sync::atomic::{AtomicUsize, Ordering};
-use std::thread;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{fence, AtomicUsize, Ordering};
+use std::collections::TryReserveError;
 
 #[derive(Debug)]
-struct AnellusInner<T: Copy> 
-{
-    r: AtomicUsize,
-    w: AtomicUsize,
-    ordering: Ordering,
+struct Cell<T> {
+    sequence: AtomicUsize,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+#[derive(Debug)]
+struct AnellusInner<T> {
+    refcount: AtomicUsize,
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
     capacity: usize,
-    ring: Vec<T>,
+    ring: Vec<Cell<T>>,
 }
 
-unsafe impl<T: Copy + Sync + Send> Sync for Anellus<T> {}
-unsafe impl<T: Copy + Sync + Send> Send for Anellus<T> {}
+// Under the sequence protocol each buffered `T` is only ever accessed by one
+// thread at a time (the producer that writes it, then the consumer that moves
+// it out), so ownership merely moves between threads: `T: Send` is sufficient
+// and sound, and requiring `T: Sync` would needlessly exclude `Send`-only
+// payloads.
+unsafe impl<T: Send> Sync for Anellus<T> {}
+unsafe impl<T: Send> Send for Anellus<T> {}
 
 #[derive(Debug)]
-pub struct Anellus<T: Copy> {
+pub struct Anellus<T> {
     ptr: *mut AnellusInner<T>,
 }
 
@@ -26,101 +41,432 @@ pub struct Anellus<T: Copy> {
 pub enum Errors {
     Empty,
     Full,
+    Alloc(TryReserveError),
+}
+
+impl From<TryReserveError> for Errors {
+    fn from(e: TryReserveError) -> Self {
+        Errors::Alloc(e)
+    }
 }
 
 type Result<T> = std::result::Result<T,Errors>;
 
 //
-// | .. | .. | .. | .. | .. | .. |
-//   ^r        ^w
+// Vyukov bounded MPMC queue: each slot carries a sequence counter that gates
+// access. A slot is writable when its sequence equals the enqueue position and
+// readable when it equals the dequeue position + 1, so a consumer can never
+// observe a slot before the matching producer has published its data.
 //
-//              ^w  ^r
+//   enqueue: seq == pos        -> claim, write, publish seq = pos + 1
+//   dequeue: seq == pos + 1    -> claim, read,  release seq = pos + capacity
 //
 
-impl<T: Copy> Clone for Anellus<T> {
+impl<T> Clone for Anellus<T> {
     fn clone(&self) -> Self {
+        let inner = unsafe { self.ptr.as_ref().unwrap() };
+        inner.refcount.fetch_add(1, Ordering::Relaxed);
         Anellus { ptr: self.ptr }
     }
 }
 
-impl<T: Copy> Anellus<T> {
+impl<T> Drop for Anellus<T> {
+    fn drop(&mut self) {
+        let inner = unsafe { self.ptr.as_ref().unwrap() };
+        if inner.refcount.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        // Last handle: synchronise with every prior `Release` decrement before
+        // we drop the buffered elements and free the box.
+        fence(Ordering::Acquire);
+        // Any slots still between the dequeue and enqueue cursors hold live
+        // values that never got pulled; run their destructors by hand since the
+        // `MaybeUninit` storage will not do it for us.
+        let mut pos = inner.dequeue_pos.load(Ordering::Relaxed);
+        let end = inner.enqueue_pos.load(Ordering::Relaxed);
+        while pos < end {
+            let cell = &inner.ring[pos % inner.capacity];
+            unsafe { (*cell.data.get()).assume_init_drop(); }
+            pos += 1;
+        }
+        unsafe { drop(Box::from_raw(self.ptr)); }
+    }
+}
+
+impl<T> Anellus<T> {
     pub fn new(size: usize) -> Self {
-        let mut res = Box::new(AnellusInner {
-            r: AtomicUsize::new(0),
-            w: AtomicUsize::new(1),
-            ordering: Ordering::SeqCst,
-            capacity: size+2,
-            ring: Vec::with_capacity(size+2)
-        });
-        unsafe {
-            res.ring.reserve_exact(res.capacity);
-            res.ring.set_len(res.capacity);
+        assert!(size > 0, "Anellus capacity must be non-zero");
+        let mut ring: Vec<Cell<T>> = Vec::with_capacity(size);
+        for i in 0..size {
+            ring.push(Cell {
+                sequence: AtomicUsize::new(i),
+                data: UnsafeCell::new(MaybeUninit::uninit()),
+            });
         }
+        let res = Box::new(AnellusInner {
+            refcount: AtomicUsize::new(1),
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+            capacity: size,
+            ring,
+        });
         Anellus { ptr: Box::into_raw(res) }
     }
 
+    /// Fallible counterpart to [`new`](Self::new): reserves the ring with
+    /// `try_reserve_exact`, returning the allocation error instead of aborting
+    /// the process when `size` cannot be satisfied.
+    pub fn try_new(size: usize) -> core::result::Result<Self, TryReserveError> {
+        assert!(size > 0, "Anellus capacity must be non-zero");
+        let mut ring: Vec<Cell<T>> = Vec::new();
+        ring.try_reserve_exact(size)?;
+        for i in 0..size {
+            ring.push(Cell {
+                sequence: AtomicUsize::new(i),
+                data: UnsafeCell::new(MaybeUninit::uninit()),
+            });
+        }
+        let res = Box::new(AnellusInner {
+            refcount: AtomicUsize::new(1),
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+            capacity: size,
+            ring,
+        });
+        Ok(Anellus { ptr: Box::into_raw(res) })
+    }
+
     pub fn pull(&self) -> Result<T> {
         let inner = unsafe { self.ptr.as_ref().unwrap() };
-        let mut value: T;
+        let mut pos = inner.dequeue_pos.load(Ordering::Relaxed);
         loop {
-            let prevr = inner.r.load(Ordering::Relaxed);
-            let prevw = inner.w.load(Ordering::Relaxed);
-            let newr = (prevr + 1) % inner.capacity;
-            if newr == prevw {
+            let cell = &inner.ring[pos % inner.capacity];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - (pos + 1) as isize;
+            if diff == 0 {
+                match inner.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // We own this slot until we release the sequence, so
+                        // moving the value out with `assume_init_read` cannot
+                        // race a producer.
+                        let value = unsafe { (*cell.data.get()).assume_init_read() };
+                        cell.sequence.store(pos + inner.capacity, Ordering::Release);
+                        return Ok(value);
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if diff < 0 {
                 return Err(Errors::Empty);
-            }
-            value = inner.ring[newr];
-            match inner.r.compare_exchange(prevr, newr, inner.ordering, Ordering::Relaxed) {
-                Ok(_) => break,
-                Err(_) => {},
+            } else {
+                pos = inner.dequeue_pos.load(Ordering::Relaxed);
             }
         }
-        Ok(value)
     }
 
     pub fn push(&mut self, value: T) -> Result<()> {
-        let inner = unsafe { self.ptr.as_mut().unwrap() };
+        let inner = unsafe { self.ptr.as_ref().unwrap() };
+        let mut pos = inner.enqueue_pos.load(Ordering::Relaxed);
         loop {
-            let prevr = inner.r.load(Ordering::Relaxed);
-            let prevw = inner.w.load(Ordering::Relaxed);
-            let neww = (prevw + 1) % inner.capacity;
-            if neww == prevr {
+            let cell = &inner.ring[pos % inner.capacity];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos as isize;
+            if diff == 0 {
+                match inner.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // We own this slot until we publish the sequence, so the
+                        // write into the cell cannot race a consumer.
+                        unsafe { (*cell.data.get()).write(value); }
+                        cell.sequence.store(pos + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if diff < 0 {
                 return Err(Errors::Full);
+            } else {
+                pos = inner.enqueue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Claim a contiguous run of slots with a single `enqueue_pos` CAS and fill
+    /// them from `items`, amortising the per-element atomic over the whole run.
+    /// Returns the number of elements pushed, which may be fewer than
+    /// `items.len()` when the queue fills up, or `Errors::Full` when no slot is
+    /// available at all.
+    pub fn push_slice(&mut self, items: &[T]) -> Result<usize>
+    where
+        T: Copy,
+    {
+        let inner = unsafe { self.ptr.as_ref().unwrap() };
+        if items.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            let pos = inner.enqueue_pos.load(Ordering::Relaxed);
+            let mut n = 0;
+            while n < items.len() {
+                let cell = &inner.ring[(pos + n) % inner.capacity];
+                let seq = cell.sequence.load(Ordering::Acquire);
+                if seq as isize - (pos + n) as isize == 0 {
+                    n += 1;
+                } else {
+                    break;
+                }
+            }
+            if n == 0 {
+                let seq = inner.ring[pos % inner.capacity].sequence.load(Ordering::Acquire);
+                if (seq as isize - pos as isize) < 0 {
+                    return Err(Errors::Full);
+                }
+                // `pos` was stale: another producer moved past it; reload.
+                continue;
+            }
+            if inner.enqueue_pos.compare_exchange_weak(
+                pos,
+                pos + n,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ).is_err() {
+                continue;
             }
-            match inner.w.compare_exchange(prevw, neww, inner.ordering, Ordering::Relaxed) {
-                Ok(_) => { 
-                    inner.ring[prevw] = value;
+            for (j, item) in items[..n].iter().enumerate() {
+                let cell = &inner.ring[(pos + j) % inner.capacity];
+                unsafe { (*cell.data.get()).write(*item); }
+                cell.sequence.store(pos + j + 1, Ordering::Release);
+            }
+            return Ok(n);
+        }
+    }
+
+    /// Claim and copy out a contiguous run of elements into `out` with a single
+    /// `dequeue_pos` CAS. Returns the number of elements written, zero when the
+    /// queue is empty.
+    pub fn pull_batch(&self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let inner = unsafe { self.ptr.as_ref().unwrap() };
+        if out.is_empty() {
+            return 0;
+        }
+        loop {
+            let pos = inner.dequeue_pos.load(Ordering::Relaxed);
+            let mut n = 0;
+            while n < out.len() {
+                let cell = &inner.ring[(pos + n) % inner.capacity];
+                let seq = cell.sequence.load(Ordering::Acquire);
+                if seq as isize - (pos + n + 1) as isize == 0 {
+                    n += 1;
+                } else {
                     break;
-                },
-                Err(_) => {}
+                }
+            }
+            if n == 0 {
+                let seq = inner.ring[pos % inner.capacity].sequence.load(Ordering::Acquire);
+                if (seq as isize - (pos + 1) as isize) < 0 {
+                    return 0;
+                }
+                // `pos` was stale: another consumer moved past it; reload.
+                continue;
+            }
+            if inner.dequeue_pos.compare_exchange_weak(
+                pos,
+                pos + n,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ).is_err() {
+                continue;
+            }
+            for (j, slot) in out[..n].iter_mut().enumerate() {
+                let cell = &inner.ring[(pos + j) % inner.capacity];
+                *slot = unsafe { (*cell.data.get()).assume_init_read() };
+                cell.sequence.store(pos + j + inner.capacity, Ordering::Release);
+            }
+            return n;
+        }
+    }
+
+    /// Return an iterator that pulls buffered elements one at a time until the
+    /// queue is observed empty.
+    pub fn drain(&self) -> Drain<'_, T> {
+        Drain { queue: self }
+    }
+}
+
+/// Draining iterator created by [`Anellus::drain`]. Yields elements until a
+/// `pull` reports the queue empty.
+pub struct Drain<'a, T> {
+    queue: &'a Anellus<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.pull().ok()
+    }
+}
+
+//
+// Inline, const-generic sibling of `Anellus`. The ring lives directly in the
+// struct as `[StaticCell; N]`, so the queue can be placed in a `static` or on
+// the stack with no allocation and no `Box::into_raw`. The slot protocol is the
+// same Vyukov per-cell sequence scheme; only the storage differs.
+//
+
+#[derive(Debug)]
+struct StaticCell<T> {
+    sequence: AtomicUsize,
+    data: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> StaticCell<T> {
+    // Used only as the repeat operand when building the `[StaticCell; N]` array,
+    // where the interior-mutable atomic is intentional.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const UNINIT: StaticCell<T> = StaticCell {
+        sequence: AtomicUsize::new(0),
+        data: UnsafeCell::new(MaybeUninit::uninit()),
+    };
+}
+
+#[derive(Debug)]
+pub struct StaticAnellus<T, const N: usize> {
+    enqueue_pos: AtomicUsize,
+    dequeue_pos: AtomicUsize,
+    ring: [StaticCell<T>; N],
+}
+
+unsafe impl<T: Sync + Send, const N: usize> Sync for StaticAnellus<T, N> {}
+unsafe impl<T: Sync + Send, const N: usize> Send for StaticAnellus<T, N> {}
+
+impl<T, const N: usize> StaticAnellus<T, N> {
+    pub const fn new() -> Self {
+        assert!(N > 0, "StaticAnellus capacity N must be non-zero");
+        let mut ring = [StaticCell::UNINIT; N];
+        let mut i = 0;
+        while i < N {
+            ring[i].sequence = AtomicUsize::new(i);
+            i += 1;
+        }
+        StaticAnellus {
+            enqueue_pos: AtomicUsize::new(0),
+            dequeue_pos: AtomicUsize::new(0),
+            ring,
+        }
+    }
+
+    pub fn pull(&self) -> Result<T> {
+        let mut pos = self.dequeue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.ring[pos % N];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - (pos + 1) as isize;
+            if diff == 0 {
+                match self.dequeue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let value = unsafe { (*cell.data.get()).assume_init_read() };
+                        cell.sequence.store(pos + N, Ordering::Release);
+                        return Ok(value);
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if diff < 0 {
+                return Err(Errors::Empty);
+            } else {
+                pos = self.dequeue_pos.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn push(&self, value: T) -> Result<()> {
+        let mut pos = self.enqueue_pos.load(Ordering::Relaxed);
+        loop {
+            let cell = &self.ring[pos % N];
+            let seq = cell.sequence.load(Ordering::Acquire);
+            let diff = seq as isize - pos as isize;
+            if diff == 0 {
+                match self.enqueue_pos.compare_exchange_weak(
+                    pos,
+                    pos + 1,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        unsafe { (*cell.data.get()).write(value); }
+                        cell.sequence.store(pos + 1, Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if diff < 0 {
+                return Err(Errors::Full);
+            } else {
+                pos = self.enqueue_pos.load(Ordering::Relaxed);
             }
         }
-        Ok(())
+    }
+}
+
+impl<T, const N: usize> Default for StaticAnellus<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for StaticAnellus<T, N> {
+    fn drop(&mut self) {
+        // We have exclusive access, so drop any elements still buffered between
+        // the dequeue and enqueue cursors; the `MaybeUninit` slots will not.
+        let mut pos = *self.dequeue_pos.get_mut();
+        let end = *self.enqueue_pos.get_mut();
+        while pos < end {
+            let cell = &self.ring[pos % N];
+            unsafe { (*cell.data.get()).assume_init_drop(); }
+            pos += 1;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::Anellus;
-    
+    use crate::StaticAnellus;
+
     #[test]
     fn read_from_empty() {
-        let r : Anellus<u32> = Anellus::new(3); 
-   
+        let r : Anellus<u32> = Anellus::new(3);
+
         assert!(r.pull().is_err());
     }
 
     #[test]
     fn basic_write() {
-        let mut r : Anellus<u32> = Anellus::new(3); 
+        let mut r : Anellus<u32> = Anellus::new(3);
 
         assert!(r.push(1).is_ok());
     }
 
     #[test]
     fn write_to_full() {
-        let mut r : Anellus<u32> = Anellus::new(3); 
-    
+        let mut r : Anellus<u32> = Anellus::new(3);
+
         assert!(r.push(1).is_ok());
         assert!(r.push(2).is_ok());
         assert!(r.push(3).is_ok());
@@ -129,8 +475,8 @@ mod tests {
 
     #[test]
     fn can_pull() {
-        let mut r : Anellus<u32> = Anellus::new(3); 
-    
+        let mut r : Anellus<u32> = Anellus::new(3);
+
         r.push(1).unwrap();
         r.push(2).unwrap();
         match r.pull() {
@@ -151,18 +497,84 @@ mod tests {
             Ok(x) => assert_eq!(x,4),
             Err(x) => panic!("{:?}", x),
         }
-        
+
+        assert!(r.pull().is_err());
+    }
+
+    #[test]
+    fn owns_strings() {
+        let mut r : Anellus<String> = Anellus::new(3);
+
+        r.push(String::from("hello")).unwrap();
+        r.push(String::from("world")).unwrap();
+        assert_eq!(r.pull().unwrap(), "hello");
+        assert_eq!(r.pull().unwrap(), "world");
+        assert!(r.pull().is_err());
+    }
+
+    #[test]
+    fn drops_buffered_elements() {
+        // A queue dropped with elements still in it must run their destructors;
+        // if it leaked instead this would report under a leak checker, and the
+        // heap-allocated `String`s would never be freed.
+        let mut r : Anellus<String> = Anellus::new(4);
+        r.push(String::from("a")).unwrap();
+        r.push(String::from("b")).unwrap();
+        drop(r);
+    }
+
+    #[test]
+    fn try_new_ok() {
+        let mut r : Anellus<u32> = Anellus::try_new(3).unwrap();
+
+        assert!(r.push(1).is_ok());
+        assert_eq!(r.pull().unwrap(), 1);
+    }
+
+    #[test]
+    fn try_new_reports_capacity_overflow() {
+        let r = Anellus::<u64>::try_new(usize::MAX);
+
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn push_slice_and_pull_batch() {
+        let mut r : Anellus<u32> = Anellus::new(5);
+
+        assert_eq!(r.push_slice(&[1,2,3]).unwrap(), 3);
+        // Only two slots remain, so the run is truncated.
+        assert_eq!(r.push_slice(&[4,5,6]).unwrap(), 2);
+        assert!(r.push_slice(&[7]).is_err());
+
+        let mut out = [0u32; 4];
+        assert_eq!(r.pull_batch(&mut out), 4);
+        assert_eq!(out, [1,2,3,4]);
+        assert_eq!(r.pull_batch(&mut out), 1);
+        assert_eq!(out[0], 5);
+        assert_eq!(r.pull_batch(&mut out), 0);
+    }
+
+    #[test]
+    fn drain_yields_until_empty() {
+        let mut r : Anellus<u32> = Anellus::new(4);
+
+        r.push(1).unwrap();
+        r.push(2).unwrap();
+        r.push(3).unwrap();
+        let drained : Vec<u32> = r.drain().collect();
+        assert_eq!(drained, vec![1,2,3]);
         assert!(r.pull().is_err());
     }
 
     fn n_to_m(n: u16, m: u16) {
         let mut r = <Anellus<u64>>::new((m+n+32) as usize); // need enough capacity for the poison pills
-        let stock : u32 = 100; 
+        let stock : u32 = 100;
         use std::thread::*;
 
         let mut producers: Vec<JoinHandle<u64>> = Vec::new();
         let mut consumers: Vec<JoinHandle<Vec<u64>>> = Vec::new();
-        
+
         for t in 0..n {
             let mut rr = r.clone();
             producers.push(spawn(move || -> u64 {
@@ -207,7 +619,7 @@ mod tests {
         for t in consumers {
             seenvalues.push(t.join().unwrap());
         }
-        
+
         let mut seenoneach : Vec<u32> = vec![0; n.into()];
         for values in seenvalues {
             let mut lastforeach: Vec<u32> = vec![0; n.into()];
@@ -216,7 +628,7 @@ mod tests {
                 let counter : u32 = (v % (u32::MAX as u64)).try_into().unwrap();
                 seenoneach[producer] += 1;
                 if counter <= lastforeach[producer] {
-                    panic!("order violation for {} was {}Â now {}", producer, lastforeach[producer], counter);
+                    panic!("order violation for {} was {}Â now {}", producer, lastforeach[producer], counter);
                 }
                 lastforeach[producer] = counter;
             }
@@ -273,6 +685,80 @@ mod tests {
         n_to_m(100,1);
     }
 
+    #[test]
+    fn static_read_from_empty() {
+        let r : StaticAnellus<u32, 3> = StaticAnellus::new();
+
+        assert!(r.pull().is_err());
+    }
+
+    #[test]
+    fn static_write_to_full() {
+        let r : StaticAnellus<u32, 3> = StaticAnellus::new();
+
+        assert!(r.push(1).is_ok());
+        assert!(r.push(2).is_ok());
+        assert!(r.push(3).is_ok());
+        assert!(r.push(4).is_err());
+    }
+
+    #[test]
+    fn static_can_pull() {
+        let r : StaticAnellus<u32, 3> = StaticAnellus::new();
+
+        r.push(1).unwrap();
+        r.push(2).unwrap();
+        match r.pull() {
+            Ok(x) => assert_eq!(x,1),
+            Err(x) => panic!("{:?}", x),
+        }
+        assert!(r.push(3).is_ok());
+        assert!(r.push(4).is_ok());
+        match r.pull() {
+            Ok(x) => assert_eq!(x,2),
+            Err(x) => panic!("{:?}", x),
+        }
+        match r.pull() {
+            Ok(x) => assert_eq!(x,3),
+            Err(x) => panic!("{:?}", x),
+        }
+        match r.pull() {
+            Ok(x) => assert_eq!(x,4),
+            Err(x) => panic!("{:?}", x),
+        }
+
+        assert!(r.pull().is_err());
+    }
+
+    static STATIC_QUEUE: StaticAnellus<u64, 64> = StaticAnellus::new();
+
+    #[test]
+    fn static_shared() {
+        use std::thread::*;
+
+        let mut producers: Vec<JoinHandle<()>> = Vec::new();
+        for t in 0..4u64 {
+            producers.push(spawn(move || {
+                let mut i = 1u64;
+                while i <= 10 {
+                    if STATIC_QUEUE.push((t << 32) + i).is_ok() {
+                        i += 1;
+                    }
+                    yield_now();
+                }
+            }));
+        }
+        for t in producers {
+            t.join().unwrap();
+        }
+
+        let mut seen = 0;
+        while STATIC_QUEUE.pull().is_ok() {
+            seen += 1;
+        }
+        assert_eq!(seen, 40);
+    }
+
     use super::*;
     use test::Bencher;
     #[allow(soft_unstable)]
@@ -281,3 +767,77 @@ mod tests {
         b.iter(|| n_to_m(1000,1));
     }
 }
+
+//
+// ThreadSanitizer stress harness. These cases are gated behind `--cfg
+// anellus_tsan` so the ordinary `cargo test` run stays cheap; run them with
+//
+//     RUSTFLAGS="-Zsanitizer=thread --cfg anellus_tsan" \
+//         cargo +nightly test -Zbuild-std --target x86_64-unknown-linux-gnu tsan
+//
+// to keep the relaxed orderings honest rather than assumed.
+//
+#[cfg(all(test, anellus_tsan))]
+mod tsan {
+    use crate::Anellus;
+    use std::thread::*;
+
+    // Drive `n` producers and `m` consumers through a small queue so that the
+    // sequence Acquire/Release pairing is exercised under data-race detection.
+    fn stress(n: u64, m: u64, stock: u64) {
+        let mut r = <Anellus<u64>>::new((n + m + 8) as usize);
+
+        let mut producers: Vec<JoinHandle<()>> = Vec::new();
+        let mut consumers: Vec<JoinHandle<()>> = Vec::new();
+
+        for t in 0..n {
+            let mut rr = r.clone();
+            producers.push(spawn(move || {
+                let mut i = 1u64;
+                while i <= stock {
+                    if rr.push((t << 32) + i).is_ok() {
+                        i += 1;
+                    }
+                    yield_now();
+                }
+            }));
+        }
+
+        for _t in 0..m {
+            let rr = r.clone();
+            consumers.push(spawn(move || loop {
+                match rr.pull() {
+                    Ok(0) => return,
+                    Ok(_) => {}
+                    Err(_) => {}
+                }
+                yield_now();
+            }));
+        }
+
+        for t in producers {
+            t.join().unwrap();
+        }
+        for _i in 0..m {
+            r.push(0).unwrap();
+        }
+        for t in consumers {
+            t.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn tsan_spsc() {
+        stress(1, 1, 1000);
+    }
+
+    #[test]
+    fn tsan_mpsc() {
+        stress(4, 1, 500);
+    }
+
+    #[test]
+    fn tsan_mpmc() {
+        stress(4, 4, 500);
+    }
+}